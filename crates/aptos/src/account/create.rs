@@ -7,23 +7,32 @@
 //!
 
 use crate::{
-    common::types::{EncodingOptions, NodeOptions, PrivateKeyInputOptions},
+    account::{
+        middleware::{
+            GasOracle, Middleware, NonceManager, RestClientMiddleware, Signer, TransactionRequest,
+        },
+        signer::{LedgerSigner, LocalAccountSigner, TransactionSigner},
+    },
+    common::{
+        faucet::FaucetClient,
+        quorum::{QuorumPolicy, QuorumProvider},
+        resolver::{NameOrAddress, ResolverCache},
+        retry::{RetryClient, RetryOptions},
+        types::{EncodingOptions, NodeOptions, PrivateKeyInputOptions},
+    },
     CliResult, Error as CommonError,
 };
 use anyhow::Error;
-use aptos_crypto::{
-    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-    PrivateKey,
-};
+use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_rest_client::{Client as RestClient, Response, Transaction};
-use aptos_sdk::{
-    transaction_builder::TransactionFactory,
-    types::{chain_id::ChainId, transaction::authenticator::AuthenticationKey, LocalAccount},
+use aptos_sdk::types::{
+    chain_id::ChainId, transaction::authenticator::AuthenticationKey,
 };
 use aptos_transaction_builder::aptos_stdlib;
 use aptos_types::account_address::AccountAddress;
 use clap::Parser;
 use reqwest;
+use std::str::FromStr;
 
 /// Command to create a new account on-chain
 ///
@@ -38,43 +47,120 @@ pub struct CreateAccount {
     #[clap(flatten)]
     node: NodeOptions,
 
-    /// Public Key of account you want to create
+    #[clap(flatten)]
+    retry_options: RetryOptions,
+
+    /// Additional fullnode URLs to query for a read quorum; repeat the flag
+    #[clap(long = "node-url")]
+    quorum_node_urls: Vec<reqwest::Url>,
+
+    /// Read quorum policy: "majority", an absolute count, or a fraction (e.g. 2/3)
+    #[clap(long, default_value = "majority")]
+    quorum: QuorumPolicy,
+
+    /// Public key, address, or registered name of the account you want to create
     public_key: String,
 
+    /// Account holding the on-chain name registry, required to resolve names
+    #[clap(long)]
+    registry_address: Option<AccountAddress>,
+
     /// Chain ID
     chain_id: u8,
 
     /// Flag for using faucet
     #[clap(long)]
     use_faucet: bool,
+
+    /// Sign with a connected Ledger hardware wallet instead of a local key
+    #[clap(long)]
+    ledger: bool,
+
+    /// BIP44 derivation path to use on the Ledger device
+    #[clap(long)]
+    derivation_path: Option<String>,
+
+    /// URL of the faucet to fund the account from
+    #[clap(long, default_value = "https://faucet.devnet.aptoslabs.com")]
+    faucet_url: reqwest::Url,
+
+    /// Amount to fund the new account with when using the faucet
+    #[clap(long, default_value_t = 0)]
+    amount: u64,
+
+    /// Memoizes resolved names across this command's lookups
+    #[clap(skip)]
+    resolver_cache: ResolverCache,
 }
 
 impl CreateAccount {
     async fn get_account(
         &self,
         account: AccountAddress,
-    ) -> Result<serde_json::Value, reqwest::Error> {
-        reqwest::get(format!("{}accounts/{}", self.node.url, account))
-            .await?
-            .json()
-            .await
+    ) -> Result<serde_json::Value, CommonError> {
+        // When extra nodes are configured, read through a quorum so a single
+        // stale or malicious fullnode cannot return a wrong account state.
+        if !self.quorum_node_urls.is_empty() {
+            let mut urls = vec![reqwest::Url::clone(&self.node.url)];
+            urls.extend(self.quorum_node_urls.iter().cloned());
+            return QuorumProvider::new(urls, self.quorum, self.retry_options)
+                .get_account(account)
+                .await;
+        }
+
+        let retry = RetryClient::new(self.retry_options);
+        let request =
+            reqwest::Client::new().get(format!("{}accounts/{}", self.node.url, account));
+        retry.send_json(request).await
     }
 
-    fn get_address(&self) -> Result<AccountAddress, String> {
-        let public_key: Ed25519PublicKey = self
+    async fn get_address(&self) -> Result<AccountAddress, Error> {
+        // Disambiguate the argument explicitly rather than relying on a public
+        // key failing to decode: a literal address is used as-is (and needs no
+        // registry), otherwise the value is either an encoded public key whose
+        // derived address we use, or a registered name resolved on-chain.
+        //
+        // Safe: `NameOrAddress::from_str` is infallible.
+        let name = match NameOrAddress::from_str(&self.public_key).unwrap() {
+            NameOrAddress::Address(address) => return Ok(address),
+            NameOrAddress::Name(name) => name,
+        };
+
+        // A non-address argument may still be an encoded public key (e.g. a
+        // base64 key, which does not parse as a hex address).
+        let decoded: Result<Ed25519PublicKey, _> = self
             .encoding_options
             .encoding
-            .decode_key(self.public_key.as_bytes().to_vec())
-            .map_err(|err| err.to_string())?;
-        let auth_key = AuthenticationKey::ed25519(&public_key);
-        Ok(AccountAddress::new(*auth_key.derived_address()))
+            .decode_key(self.public_key.as_bytes().to_vec());
+        if let Ok(public_key) = decoded {
+            let auth_key = AuthenticationKey::ed25519(&public_key);
+            return Ok(AccountAddress::new(*auth_key.derived_address()));
+        }
+
+        let registry = self.registry_address.ok_or_else(|| {
+            Error::new(CommonError::UnexpectedError(
+                "'--registry-address' is required to resolve a name".to_string(),
+            ))
+        })?;
+        NameOrAddress::Name(name)
+            .resolve(&self.node.url, registry, self.retry_options, &self.resolver_cache)
+            .await
+            .map_err(Error::new)
     }
 
     async fn get_sequence_number(&self, account: AccountAddress) -> Result<u64, CommonError> {
-        let account_response = self
-            .get_account(account)
-            .await
-            .map_err(|err| CommonError::UnexpectedError(err.to_string()))?;
+        // With extra nodes, quorum on the sequence number itself rather than the
+        // whole account resource, which would spuriously diverge on unrelated
+        // fields between honest nodes a ledger version apart.
+        if !self.quorum_node_urls.is_empty() {
+            let mut urls = vec![reqwest::Url::clone(&self.node.url)];
+            urls.extend(self.quorum_node_urls.iter().cloned());
+            return QuorumProvider::new(urls, self.quorum, self.retry_options)
+                .get_sequence_number(account)
+                .await;
+        }
+
+        let account_response = self.get_account(account).await?;
         let sequence_number = &account_response["sequence_number"];
         match sequence_number.as_str() {
             Some(number) => Ok(number.parse::<u64>().unwrap()),
@@ -84,65 +170,78 @@ impl CreateAccount {
         }
     }
 
+    /// Build the signing backend selected on the command line: a Ledger device
+    /// when `--ledger` is set, otherwise the software private key.
+    async fn signer(&self) -> Result<Box<dyn TransactionSigner>, Error> {
+        if self.ledger {
+            let ledger = LedgerSigner::connect(self.derivation_path.clone())
+                .await
+                .map_err(Error::new)?;
+            Ok(Box::new(ledger))
+        } else {
+            let private_key = self
+                .private_key_input_options
+                .extract_private_key(self.encoding_options.encoding)?
+                .ok_or(CommonError::UnexpectedError(
+                    "One of ['--private-key', '--private-key-file', '--use-faucet', '--ledger'] must be provided"
+                        .to_string(),
+                ))?;
+            Ok(Box::new(LocalAccountSigner::new(private_key)))
+        }
+    }
+
     async fn post_account(
         &self,
         address: AccountAddress,
-        sender_key: Ed25519PrivateKey,
-        sender_address: AccountAddress,
+        signer: Box<dyn TransactionSigner>,
         sequence_number: u64,
     ) -> Result<Response<Transaction>, Error> {
         let client = RestClient::new(reqwest::Url::clone(&self.node.url));
         let chain_id = ChainId::new(self.chain_id);
-        let transaction_factory = TransactionFactory::new(chain_id)
-            .with_gas_unit_price(1)
-            .with_max_gas_amount(1000);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction = sender_account.sign_with_transaction_builder(
-            transaction_factory
-                .payload(aptos_stdlib::encode_create_account_script_function(address)),
+        let sender_address = signer.sender_address();
+
+        // Assemble the submission stack: the NonceManager caches and increments
+        // the sequence number, the GasOracle fills in gas parameters, and the
+        // Signer signs (with a local key or the Ledger) — in that order on the way
+        // down, so the signature covers the nonce and gas before the request
+        // reaches the REST client.
+        let stack = NonceManager::new(
+            GasOracle::new(Signer::new(
+                RestClientMiddleware::new(client),
+                signer,
+                chain_id,
+            )),
+            sender_address,
+            Some(sequence_number),
         );
-        client.submit_and_wait(&transaction).await
+        stack
+            .send_transaction(TransactionRequest::new(
+                aptos_stdlib::encode_create_account_script_function(address),
+            ))
+            .await
+            .map_err(Error::new)
     }
 
     async fn create_account_with_faucet(self, address: AccountAddress) -> Result<String, Error> {
-        let response = reqwest::Client::new()
-            // TODO: Currently, we are just using mint 0 to create an account using the faucet
-            // We should make a faucet endpoint for creating an account
-            .post(format!(
-                "{}/mint?amount={}&auth_key={}",
-                "https://faucet.devnet.aptoslabs.com", "0", address
-            ))
-            .send()
-            .await?;
-        if response.status() == 200 {
-            Ok(response.status().to_string())
-        } else {
-            Err(Error::new(CommonError::UnexpectedError(format!(
-                "Faucet issue: {}",
-                response.status()
-            ))))
-        }
+        let faucet = FaucetClient::new_with_retry(
+            self.faucet_url.clone(),
+            reqwest::Url::clone(&self.node.url),
+            self.retry_options,
+        );
+        faucet.fund(address, self.amount).await.map_err(Error::new)?;
+        Ok("Success".to_string())
     }
 
     async fn create_account_with_key(self, address: AccountAddress) -> Result<String, Error> {
-        let private_key = self
-            .private_key_input_options
-            .extract_private_key(self.encoding_options.encoding)?
-            .ok_or(CommonError::UnexpectedError(
-                "One of ['--private-key', '--private-key-file', '--use-faucet'] must be provided"
-                    .to_string(),
-            ))?;
-        let sender_address =
-            AuthenticationKey::ed25519(&private_key.public_key()).derived_address();
-        let sender_address = AccountAddress::new(*sender_address);
-        let sequence_number = self.get_sequence_number(sender_address).await;
-        match sequence_number {
-            Ok(sequence_number) => self
-                .post_account(address, private_key, sender_address, sequence_number)
-                .await
-                .map(|_| "Success".to_string()),
-            Err(err) => Err(Error::new(err)),
-        }
+        let signer = self.signer().await?;
+        let sender_address = signer.sender_address();
+        let sequence_number = self
+            .get_sequence_number(sender_address)
+            .await
+            .map_err(Error::new)?;
+        self.post_account(address, signer, sequence_number)
+            .await
+            .map(|_| "Success".to_string())
     }
 
     async fn execute_inner(self, address: AccountAddress) -> Result<String, Error> {
@@ -154,7 +253,7 @@ impl CreateAccount {
     }
 
     pub async fn execute(self) -> CliResult {
-        let address = self.get_address()?;
+        let address = self.get_address().await.map_err(|err| err.to_string())?;
         self.execute_inner(address)
             .await
             .map(|_| format!("Account Created at {}", address))
@@ -0,0 +1,331 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A composable middleware stack for submitting transactions.
+//!
+//! Every command used to duplicate the same four steps: fetch the sequence
+//! number, fill in the gas price and limit, sign, then submit and wait.  This
+//! module factors that into a stack of layers à la ethers' `Middleware` trait.
+//! Each layer wraps an inner middleware, fills in the part of the request it is
+//! responsible for, and delegates the actual submission downwards:
+//!
+//! ```ignore
+//! let client = NonceManager::new(
+//!     GasOracle::new(Signer::new(RestClientMiddleware::new(rest_client), signer, chain_id)),
+//!     address,
+//!     initial_sequence_number,
+//! );
+//! client.send_transaction(request).await?;
+//! ```
+//!
+//! The sequence number, gas parameters, and signature are filled in on the way
+//! down — the [`Signer`] sits below the [`NonceManager`] and [`GasOracle`] so it
+//! signs a request that already carries its nonce and gas.  The innermost
+//! [`RestClientMiddleware`] then talks to the node; [`Response`]s flow back up.
+
+use crate::{account::signer::TransactionSigner, Error as CliError};
+use aptos_rest_client::{aptos_api_types::AptosErrorCode, Client as RestClient, Response, Transaction};
+use aptos_sdk::{
+    transaction_builder::TransactionFactory,
+    types::{
+        chain_id::ChainId,
+        transaction::{SignedTransaction, TransactionPayload},
+    },
+};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+/// A transaction as it flows down through the middleware stack.
+///
+/// Each field starts out `None` and is populated by the layer responsible for
+/// it.  By the time the request reaches [`RestClientMiddleware`] it carries a
+/// fully signed transaction.
+#[derive(Debug, Default, Clone)]
+pub struct TransactionRequest {
+    /// The move payload to execute.
+    pub payload: Option<TransactionPayload>,
+    /// Sequence number, filled in by the [`NonceManager`].
+    pub sequence_number: Option<u64>,
+    /// Gas unit price, filled in by the [`GasOracle`].
+    pub gas_unit_price: Option<u64>,
+    /// Maximum gas to spend, filled in by the [`GasOracle`].
+    pub max_gas_amount: Option<u64>,
+    /// The signed transaction, produced by the [`Signer`].
+    pub signed_transaction: Option<SignedTransaction>,
+}
+
+impl TransactionRequest {
+    /// Build a request for the given payload with every other field left for the
+    /// stack to fill in.
+    pub fn new(payload: TransactionPayload) -> Self {
+        Self {
+            payload: Some(payload),
+            ..Default::default()
+        }
+    }
+}
+
+/// A single layer of the submission stack.
+///
+/// The default methods simply delegate to [`Middleware::inner`]; a layer
+/// overrides [`Middleware::send_transaction`] to fill in the field it owns before
+/// delegating (the [`NonceManager`] also observes the response to invalidate its
+/// cache).
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// The middleware this layer wraps.
+    type Inner: Middleware;
+
+    /// The inner middleware, used by the default method implementations.
+    fn inner(&self) -> &Self::Inner;
+
+    /// The REST client at the bottom of the stack.
+    fn rest_client(&self) -> &RestClient {
+        self.inner().rest_client()
+    }
+
+    /// Populate any fields this layer owns, then recurse down the stack.
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+    ) -> Result<Response<Transaction>, CliError> {
+        self.inner().send_transaction(request).await
+    }
+}
+
+/// The innermost layer: submits the signed transaction and waits for it.
+pub struct RestClientMiddleware {
+    client: RestClient,
+}
+
+impl RestClientMiddleware {
+    pub fn new(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Middleware for RestClientMiddleware {
+    // The base layer has no inner middleware; it is its own `Inner` and never
+    // recurses, mirroring how ethers' `Provider` sits at the bottom of a stack.
+    type Inner = Self;
+
+    fn inner(&self) -> &Self::Inner {
+        self
+    }
+
+    fn rest_client(&self) -> &RestClient {
+        &self.client
+    }
+
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+    ) -> Result<Response<Transaction>, CliError> {
+        let signed = request.signed_transaction.ok_or_else(|| {
+            CliError::UnexpectedError(
+                "Transaction reached the REST client unsigned; is the stack missing a Signer layer?"
+                    .to_string(),
+            )
+        })?;
+        self.client
+            .submit_and_wait(&signed)
+            .await
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))
+    }
+}
+
+/// Caches and auto-increments the account sequence number.
+///
+/// Nonce allocation is serialized behind a mutex so concurrent submissions each
+/// get a distinct, monotonically increasing sequence number.  When the node
+/// reports that the number was too old or too new the cache is invalidated, the
+/// on-chain value re-fetched, and the submission retried once.
+pub struct NonceManager<M> {
+    inner: M,
+    address: aptos_types::account_address::AccountAddress,
+    cached: Mutex<Option<u64>>,
+}
+
+impl<M> NonceManager<M> {
+    /// Build a nonce manager for `address`, optionally seeded with a sequence
+    /// number already read from the chain so the first submission avoids an
+    /// extra round-trip.
+    pub fn new(
+        inner: M,
+        address: aptos_types::account_address::AccountAddress,
+        initial: Option<u64>,
+    ) -> Self {
+        Self {
+            inner,
+            address,
+            cached: Mutex::new(initial),
+        }
+    }
+
+    /// Reserve the next sequence number, fetching from the node if the cache is
+    /// cold.
+    async fn allocate(&self, client: &RestClient) -> Result<u64, CliError> {
+        let mut cached = self.cached.lock().await;
+        let next = match *cached {
+            Some(number) => number,
+            None => self.fetch(client).await?,
+        };
+        *cached = Some(next + 1);
+        Ok(next)
+    }
+
+    /// Drop the cached value so the next allocation re-reads the chain.
+    async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    async fn fetch(&self, client: &RestClient) -> Result<u64, CliError> {
+        client
+            .get_account(self.address)
+            .await
+            .map(|response| response.into_inner().sequence_number)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for NonceManager<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<Response<Transaction>, CliError> {
+        let client = self.rest_client();
+        request.sequence_number = Some(self.allocate(client).await?);
+        // Keep a pristine copy (payload and nonce, no gas/signature filled in yet) so a
+        // retry can re-run the full build+sign through the inner layers rather than
+        // re-stamping an already-signed request.
+        let template = request.clone();
+        match self.inner.send_transaction(request).await {
+            Err(err) if is_sequence_number_error(&err) => {
+                // The cached value diverged from the chain; refetch and retry once.
+                self.invalidate().await;
+                let retry = TransactionRequest {
+                    sequence_number: Some(self.allocate(client).await?),
+                    ..template
+                };
+                self.inner.send_transaction(retry).await
+            }
+            other => other,
+        }
+    }
+}
+
+/// Returns true when the node rejected a transaction because its sequence number
+/// was out of step with the chain, which the [`NonceManager`] recovers from.
+fn is_sequence_number_error(err: &CliError) -> bool {
+    let message = err.to_string();
+    message.contains(AptosErrorCode::SequenceNumberTooOld.to_string().as_str())
+        || message.contains("SEQUENCE_NUMBER_TOO_OLD")
+        || message.contains("SEQUENCE_NUMBER_TOO_NEW")
+}
+
+/// Fills in the gas unit price and maximum gas amount when a command leaves them
+/// unset.
+pub struct GasOracle<M> {
+    inner: M,
+    gas_unit_price: u64,
+    max_gas_amount: u64,
+}
+
+impl<M> GasOracle<M> {
+    pub fn new(inner: M) -> Self {
+        // The previous hard-coded defaults from `CreateAccount::post_account`.
+        Self {
+            inner,
+            gas_unit_price: 1,
+            max_gas_amount: 1000,
+        }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for GasOracle<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<Response<Transaction>, CliError> {
+        request
+            .gas_unit_price
+            .get_or_insert(self.gas_unit_price);
+        request
+            .max_gas_amount
+            .get_or_insert(self.max_gas_amount);
+        self.inner.send_transaction(request).await
+    }
+}
+
+/// Signs the assembled transaction with the configured signing backend, which
+/// may be an in-memory key or a hardware wallet.
+pub struct Signer<M> {
+    inner: M,
+    signer: Box<dyn TransactionSigner>,
+    chain_id: ChainId,
+}
+
+impl<M> Signer<M> {
+    pub fn new(inner: M, signer: Box<dyn TransactionSigner>, chain_id: ChainId) -> Self {
+        Self {
+            inner,
+            signer,
+            chain_id,
+        }
+    }
+
+    /// The address transactions are sent from, derived from the signer.
+    pub fn sender_address(&self) -> aptos_types::account_address::AccountAddress {
+        self.signer.sender_address()
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for Signer<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<Response<Transaction>, CliError> {
+        let payload = request.payload.take().ok_or_else(|| {
+            CliError::UnexpectedError("Transaction has no payload to sign".to_string())
+        })?;
+
+        let mut factory = TransactionFactory::new(self.chain_id);
+        if let Some(price) = request.gas_unit_price {
+            factory = factory.with_gas_unit_price(price);
+        }
+        if let Some(max_gas) = request.max_gas_amount {
+            factory = factory.with_max_gas_amount(max_gas);
+        }
+
+        let raw = factory
+            .payload(payload)
+            .sender(self.signer.sender_address())
+            .sequence_number(request.sequence_number.unwrap_or_default())
+            .build();
+        request.signed_transaction = Some(self.signer.sign(raw).await?);
+
+        self.inner.send_transaction(request).await
+    }
+}
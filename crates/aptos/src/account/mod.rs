@@ -0,0 +1,8 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Account related commands and the submission plumbing shared between them
+
+pub mod create;
+pub mod middleware;
+pub mod signer;
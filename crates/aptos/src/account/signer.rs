@@ -0,0 +1,180 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing backends for transactions.
+//!
+//! A [`TransactionSigner`] abstracts over where the private key lives so the
+//! commands can treat a software key and a hardware wallet identically. The
+//! [`LocalAccountSigner`] keeps an Ed25519 key in memory; the [`LedgerSigner`]
+//! (modeled on ethers' Ledger integration) forwards the raw transaction bytes
+//! to a connected device for signing and never exposes the key.
+
+use crate::Error;
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    PrivateKey, SigningKey,
+};
+use aptos_sdk::types::transaction::{
+    authenticator::AuthenticationKey, RawTransaction, SignedTransaction,
+};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+
+/// Something that can sign a [`RawTransaction`] on behalf of an account.
+///
+/// Mirrors the surface of `LocalAccount::sign_with_transaction_builder`: callers
+/// build the raw transaction, hand it here, and get back a [`SignedTransaction`]
+/// ready to submit.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// The address transactions are sent from, derived from the signer's public
+    /// key.
+    fn sender_address(&self) -> AccountAddress {
+        AccountAddress::new(*AuthenticationKey::ed25519(&self.public_key()).derived_address())
+    }
+
+    /// The signer's public key.
+    fn public_key(&self) -> Ed25519PublicKey;
+
+    /// Sign `raw` and return the submittable transaction.
+    async fn sign(&self, raw: RawTransaction) -> Result<SignedTransaction, Error>;
+}
+
+/// A signer backed by an in-memory Ed25519 private key.
+pub struct LocalAccountSigner {
+    private_key: Ed25519PrivateKey,
+    public_key: Ed25519PublicKey,
+}
+
+impl LocalAccountSigner {
+    pub fn new(private_key: Ed25519PrivateKey) -> Self {
+        let public_key = private_key.public_key();
+        Self {
+            private_key,
+            public_key,
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for LocalAccountSigner {
+    fn public_key(&self) -> Ed25519PublicKey {
+        self.public_key.clone()
+    }
+
+    async fn sign(&self, raw: RawTransaction) -> Result<SignedTransaction, Error> {
+        let signature = self
+            .private_key
+            .sign(&raw)
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        Ok(SignedTransaction::new(
+            raw,
+            self.public_key.clone(),
+            signature,
+        ))
+    }
+}
+
+/// A signer backed by a Ledger hardware wallet.
+///
+/// The public key is read from the device at construction so the sender address
+/// can be derived without the key ever leaving the device; signing sends the
+/// BCS-serialized raw transaction to the device and returns its signature.
+pub struct LedgerSigner {
+    transport: ledger_transport_hid::TransportNativeHID,
+    derivation_path: String,
+    public_key: Ed25519PublicKey,
+}
+
+/// The Aptos Ledger app's instruction class.
+const LEDGER_CLA: u8 = 0x5b;
+/// Instruction: fetch the public key at a derivation path.
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+/// Instruction: sign a transaction.
+const INS_SIGN_TXN: u8 = 0x03;
+/// The default BIP44 path for the first Aptos account.
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/637'/0'/0'/0'";
+
+impl LedgerSigner {
+    /// Connect to the first available Ledger device and read the public key at
+    /// `derivation_path` (defaulting to the first Aptos account).
+    pub async fn connect(derivation_path: Option<String>) -> Result<Self, Error> {
+        let derivation_path =
+            derivation_path.unwrap_or_else(|| DEFAULT_DERIVATION_PATH.to_string());
+        let hid = ledger_transport_hid::hidapi::HidApi::new()
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        let transport = ledger_transport_hid::TransportNativeHID::new(&hid)
+            .map_err(|err| Error::UnexpectedError(format!("No Ledger device found: {}", err)))?;
+
+        let response = transport
+            .exchange(&ledger_apdu::APDUCommand {
+                cla: LEDGER_CLA,
+                ins: INS_GET_PUBLIC_KEY,
+                p1: 0x00,
+                p2: 0x00,
+                data: encode_derivation_path(&derivation_path)?,
+            })
+            .await
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        let public_key = Ed25519PublicKey::try_from(response.data())
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+
+        Ok(Self {
+            transport,
+            derivation_path,
+            public_key,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for LedgerSigner {
+    fn public_key(&self) -> Ed25519PublicKey {
+        self.public_key.clone()
+    }
+
+    async fn sign(&self, raw: RawTransaction) -> Result<SignedTransaction, Error> {
+        let mut data = encode_derivation_path(&self.derivation_path)?;
+        data.extend(
+            bcs::to_bytes(&raw).map_err(|err| Error::UnexpectedError(err.to_string()))?,
+        );
+
+        let response = self
+            .transport
+            .exchange(&ledger_apdu::APDUCommand {
+                cla: LEDGER_CLA,
+                ins: INS_SIGN_TXN,
+                p1: 0x00,
+                p2: 0x00,
+                data,
+            })
+            .await
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        let signature = Ed25519Signature::try_from(response.data())
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+
+        Ok(SignedTransaction::new(
+            raw,
+            self.public_key.clone(),
+            signature,
+        ))
+    }
+}
+
+/// Encode a `m/44'/637'/.../...` derivation path into the length-prefixed list
+/// of big-endian u32 components the device expects.
+fn encode_derivation_path(path: &str) -> Result<Vec<u8>, Error> {
+    let components: Vec<&str> = path.trim_start_matches("m/").split('/').collect();
+    let mut encoded = Vec::with_capacity(1 + components.len() * 4);
+    encoded.push(components.len() as u8);
+    for component in components {
+        let hardened = component.ends_with('\'');
+        let index: u32 = component
+            .trim_end_matches('\'')
+            .parse()
+            .map_err(|_| Error::UnexpectedError(format!("Invalid derivation path: {}", path)))?;
+        let index = if hardened { index | 0x8000_0000 } else { index };
+        encoded.extend_from_slice(&index.to_be_bytes());
+    }
+    Ok(encoded)
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A client for the devnet/testnet faucet.
+//!
+//! Mirrors Diem's faucet client: it is constructed from a faucet URL and the
+//! node URL, POSTs a mint request with `return_txns=true`, and waits on each
+//! transaction hash the faucet returns (the create-account transaction and the
+//! mint transaction) via the REST client before reporting success.
+
+use crate::{
+    common::retry::{RetryClient, RetryOptions},
+    Error,
+};
+use aptos_rest_client::Client as RestClient;
+use aptos_types::account_address::AccountAddress;
+use reqwest::Url;
+
+/// A configurable faucet client that funds accounts and waits for the minted
+/// transactions to commit.
+pub struct FaucetClient {
+    faucet_url: Url,
+    rest_client: RestClient,
+    retry_client: RetryClient,
+}
+
+impl FaucetClient {
+    /// Build a faucet client pointed at `faucet_url`, using `node_url` to wait on
+    /// the transactions the faucet submits.
+    pub fn new(faucet_url: Url, node_url: Url) -> Self {
+        Self::new_with_retry(faucet_url, node_url, RetryOptions::default())
+    }
+
+    /// Build a faucet client with explicit retry tuning for the mint request.
+    pub fn new_with_retry(faucet_url: Url, node_url: Url, retry: RetryOptions) -> Self {
+        Self {
+            faucet_url,
+            rest_client: RestClient::new(node_url),
+            retry_client: RetryClient::new(retry),
+        }
+    }
+
+    /// Fund `auth_key` with `amount`, waiting until every transaction the faucet
+    /// returns has committed.
+    ///
+    /// The faucet mints the requested amount and, for a fresh account, also
+    /// submits the create-account transaction; `return_txns=true` asks it to
+    /// return the hashes of both so we can wait on them.
+    pub async fn fund(&self, auth_key: AccountAddress, amount: u64) -> Result<(), Error> {
+        let mut mint_url = self
+            .faucet_url
+            .join("mint")
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        mint_url.set_query(Some(&format!(
+            "amount={}&auth_key={}&return_txns=true",
+            amount, auth_key
+        )));
+
+        let response = self
+            .retry_client
+            .send(reqwest::Client::new().post(mint_url))
+            .await?;
+        if !response.status().is_success() {
+            return Err(Error::UnexpectedError(format!(
+                "Faucet issue: {}",
+                response.status()
+            )));
+        }
+
+        let hashes: Vec<String> = response
+            .json()
+            .await
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        for hash in hashes {
+            self.rest_client
+                .wait_for_transaction_by_hash(&hash)
+                .await
+                .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
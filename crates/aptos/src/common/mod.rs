@@ -0,0 +1,10 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types and helpers shared across the CLI commands
+
+pub mod faucet;
+pub mod quorum;
+pub mod resolver;
+pub mod retry;
+pub mod types;
@@ -0,0 +1,143 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A quorum REST client that reads from several fullnodes and requires
+//! agreement.
+//!
+//! A single stale or malicious fullnode can silently return a wrong sequence
+//! number or account state. `QuorumProvider` issues the same read to every
+//! configured node concurrently and only returns a value once the configured
+//! threshold of nodes agree on it, surfacing a divergence error otherwise.
+
+use crate::{
+    common::retry::{RetryClient, RetryOptions},
+    Error,
+};
+use aptos_types::account_address::AccountAddress;
+use futures::future::join_all;
+use reqwest::Url;
+use std::str::FromStr;
+
+/// How many nodes must agree before a read is accepted.
+#[derive(Clone, Copy, Debug)]
+pub enum QuorumPolicy {
+    /// Strictly more than half of the nodes.
+    Majority,
+    /// An absolute number of nodes.
+    Count(usize),
+    /// A fraction of the nodes, rounded up.
+    Fraction(f64),
+}
+
+impl QuorumPolicy {
+    /// The number of agreeing nodes required out of `total`.
+    pub fn threshold(&self, total: usize) -> usize {
+        match self {
+            QuorumPolicy::Majority => total / 2 + 1,
+            QuorumPolicy::Count(count) => (*count).min(total),
+            QuorumPolicy::Fraction(fraction) => {
+                ((fraction * total as f64).ceil() as usize).clamp(1, total)
+            }
+        }
+    }
+}
+
+impl FromStr for QuorumPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("majority") {
+            Ok(QuorumPolicy::Majority)
+        } else if let Some((num, den)) = s.split_once('/') {
+            let num: f64 = num.trim().parse().map_err(|_| format!("Invalid quorum: {}", s))?;
+            let den: f64 = den.trim().parse().map_err(|_| format!("Invalid quorum: {}", s))?;
+            if den == 0.0 {
+                return Err(format!("Invalid quorum: {}", s));
+            }
+            Ok(QuorumPolicy::Fraction(num / den))
+        } else if s.contains('.') {
+            s.parse::<f64>()
+                .map(QuorumPolicy::Fraction)
+                .map_err(|_| format!("Invalid quorum: {}", s))
+        } else {
+            s.parse::<usize>()
+                .map(QuorumPolicy::Count)
+                .map_err(|_| format!("Invalid quorum: {}", s))
+        }
+    }
+}
+
+/// Reads account state from several nodes and enforces a quorum.
+pub struct QuorumProvider {
+    urls: Vec<Url>,
+    policy: QuorumPolicy,
+    retry: RetryOptions,
+}
+
+impl QuorumProvider {
+    pub fn new(urls: Vec<Url>, policy: QuorumPolicy, retry: RetryOptions) -> Self {
+        Self {
+            urls,
+            policy,
+            retry,
+        }
+    }
+
+    /// Fetch an account's JSON (`accounts/{address}`), requiring quorum agreement.
+    pub async fn get_account(
+        &self,
+        account: AccountAddress,
+    ) -> Result<serde_json::Value, Error> {
+        self.quorum_read(|url| async move {
+            let retry = RetryClient::new(self.retry);
+            let request = reqwest::Client::new().get(format!("{}accounts/{}", url, account));
+            retry.send_json::<serde_json::Value>(request).await
+        })
+        .await
+    }
+
+    /// Fetch an account's sequence number, requiring quorum agreement.
+    ///
+    /// Agreement is enforced on the sequence number itself rather than the whole
+    /// account resource, so two honest nodes a ledger version apart (differing in
+    /// unrelated fields) still agree as long as their sequence numbers match.
+    pub async fn get_sequence_number(&self, account: AccountAddress) -> Result<u64, Error> {
+        self.quorum_read(|url| async move {
+            let retry = RetryClient::new(self.retry);
+            let request = reqwest::Client::new().get(format!("{}accounts/{}", url, account));
+            let response = retry.send_json::<serde_json::Value>(request).await?;
+            response["sequence_number"]
+                .as_str()
+                .and_then(|number| number.parse::<u64>().ok())
+                .ok_or_else(|| Error::UnexpectedError("Sequence number not found".to_string()))
+        })
+        .await
+    }
+
+    /// Run `read` against every node concurrently and return the value that a
+    /// threshold of nodes agree on.
+    async fn quorum_read<T, F, Fut>(&self, read: F) -> Result<T, Error>
+    where
+        T: Clone + PartialEq,
+        F: Fn(Url) -> Fut,
+        Fut: std::future::Future<Output = Result<T, Error>>,
+    {
+        let total = self.urls.len();
+        let threshold = self.policy.threshold(total);
+        let results = join_all(self.urls.iter().cloned().map(&read)).await;
+        let values: Vec<T> = results.into_iter().filter_map(Result::ok).collect();
+
+        for candidate in &values {
+            let agreeing = values.iter().filter(|value| *value == candidate).count();
+            if agreeing >= threshold {
+                return Ok(candidate.clone());
+            }
+        }
+
+        Err(Error::UnexpectedError(format!(
+            "Nodes diverged: no value agreed on by {} of {} nodes",
+            threshold, total
+        )))
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-chain name resolution for address-typed arguments.
+//!
+//! Inspired by ENS-style name lookups, this lets a command accept a memorable
+//! name wherever it expects an [`AccountAddress`]. A [`NameOrAddress`] parses as
+//! either a literal address or a name; [`NameOrAddress::resolve`] looks the name
+//! up in an on-chain name registry resource and substitutes the stored address
+//! before the transaction is built. Resolutions are memoized in a
+//! [`ResolverCache`] so repeated use of the same name hits the node once.
+
+use crate::{
+    common::retry::{RetryClient, RetryOptions},
+    Error,
+};
+use aptos_types::account_address::AccountAddress;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Mutex,
+};
+
+/// The resource holding the name -> address mappings, read from the registry
+/// account. Its `data.names` object maps each registered name to an address.
+const NAME_REGISTRY_RESOURCE: &str = "0x1::name_registry::NameRegistry";
+
+/// An argument that is either a literal address or a name to resolve on-chain.
+#[derive(Clone, Debug)]
+pub enum NameOrAddress {
+    /// A literal account address, used as-is.
+    Address(AccountAddress),
+    /// A registered name, resolved against the on-chain registry.
+    Name(String),
+}
+
+impl FromStr for NameOrAddress {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Anything that parses as an address is taken literally; everything else
+        // is treated as a name to resolve later.
+        Ok(match AccountAddress::from_str(s) {
+            Ok(address) => NameOrAddress::Address(address),
+            Err(_) => NameOrAddress::Name(s.to_string()),
+        })
+    }
+}
+
+impl NameOrAddress {
+    /// Resolve to a concrete address, querying the registry at `registry_address`
+    /// for names and caching the result.
+    pub async fn resolve(
+        &self,
+        node_url: &reqwest::Url,
+        registry_address: AccountAddress,
+        retry: RetryOptions,
+        cache: &ResolverCache,
+    ) -> Result<AccountAddress, Error> {
+        let name = match self {
+            NameOrAddress::Address(address) => return Ok(*address),
+            NameOrAddress::Name(name) => name,
+        };
+
+        if let Some(address) = cache.get(name) {
+            return Ok(address);
+        }
+
+        let resource_url = node_url
+            .join(&format!(
+                "accounts/{}/resource/{}",
+                registry_address, NAME_REGISTRY_RESOURCE
+            ))
+            .map_err(|err| Error::UnexpectedError(err.to_string()))?;
+        // Route the registry read through the RetryClient so a transient
+        // 429/5xx here backs off and retries rather than hard-failing resolution.
+        let request = reqwest::Client::new().get(resource_url);
+        let resource: serde_json::Value = RetryClient::new(retry).send_json(request).await?;
+
+        let address = resource["data"]["names"][name]
+            .as_str()
+            .ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "Name '{}' is not registered in the registry at {}",
+                    name, registry_address
+                ))
+            })
+            .and_then(|raw| {
+                AccountAddress::from_str(raw)
+                    .map_err(|err| Error::UnexpectedError(err.to_string()))
+            })?;
+
+        cache.insert(name.clone(), address);
+        Ok(address)
+    }
+}
+
+/// A small memoization cache for resolved names, shared across a command's
+/// lookups.
+#[derive(Debug, Default)]
+pub struct ResolverCache {
+    entries: Mutex<HashMap<String, AccountAddress>>,
+}
+
+impl ResolverCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get(&self, name: &str) -> Option<AccountAddress> {
+        self.entries.lock().unwrap().get(name).copied()
+    }
+
+    fn insert(&self, name: String, address: AccountAddress) {
+        self.entries.lock().unwrap().insert(name, address);
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A retrying HTTP wrapper for the REST and faucet layers.
+//!
+//! Modeled on ethers' retry policy: requests are retried with exponential
+//! backoff and jitter, a [`RetryPolicy`] decides which failures are worth
+//! retrying versus terminal, and a `Retry-After` header (when present) overrides
+//! the computed backoff.
+
+use crate::Error;
+use rand::Rng;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Tunables for [`RetryClient`], surfaced as `--max-retries` and
+/// `--retry-backoff-ms` on the commands.
+#[derive(Clone, Copy, Debug, clap::Parser)]
+pub struct RetryOptions {
+    /// Maximum number of times a retryable request is re-attempted
+    #[clap(long, default_value_t = 5)]
+    pub max_retries: u32,
+
+    /// Base backoff between retries, in milliseconds; doubles each attempt
+    #[clap(long, default_value_t = 50)]
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            retry_backoff_ms: 50,
+        }
+    }
+}
+
+/// Classifies failures as retryable (transient) or terminal.
+pub trait RetryPolicy: Send + Sync {
+    /// Whether a transport-level error (connection reset, timeout, ...) should be
+    /// retried.
+    fn should_retry_error(&self, err: &reqwest::Error) -> bool;
+
+    /// Whether a completed response with the given status should be retried.
+    fn should_retry_status(&self, status: StatusCode) -> bool;
+}
+
+/// The default policy: retry connection resets, timeouts, and 429/502/503
+/// responses. A JSON deserialization failure is treated as retryable too, since
+/// it usually indicates a partial response from a node behind a load balancer.
+pub struct DefaultRetryPolicy;
+
+impl RetryPolicy for DefaultRetryPolicy {
+    fn should_retry_error(&self, err: &reqwest::Error) -> bool {
+        err.is_timeout() || err.is_connect() || err.is_decode()
+    }
+
+    fn should_retry_status(&self, status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE
+        )
+    }
+}
+
+/// Wraps an HTTP request with retry-with-backoff semantics.
+pub struct RetryClient<P = DefaultRetryPolicy> {
+    options: RetryOptions,
+    policy: P,
+}
+
+impl RetryClient<DefaultRetryPolicy> {
+    pub fn new(options: RetryOptions) -> Self {
+        Self {
+            options,
+            policy: DefaultRetryPolicy,
+        }
+    }
+}
+
+impl<P: RetryPolicy> RetryClient<P> {
+    pub fn with_policy(options: RetryOptions, policy: P) -> Self {
+        Self { options, policy }
+    }
+
+    /// Send `request`, retrying transient failures up to `max_retries` times.
+    ///
+    /// The request builder must be cloneable (no streaming body) so each attempt
+    /// gets a fresh request.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, Error> {
+        let mut attempt = 0;
+        loop {
+            let builder = request.try_clone().ok_or_else(|| {
+                Error::UnexpectedError("Request body is not retryable (not cloneable)".to_string())
+            })?;
+
+            match builder.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if self.policy.should_retry_status(status) && attempt < self.options.max_retries
+                    {
+                        let delay = self
+                            .retry_after(&response)
+                            .unwrap_or_else(|| self.backoff(attempt));
+                        attempt += 1;
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    return Ok(response);
+                }
+                Err(err) => {
+                    if self.policy.should_retry_error(&err) && attempt < self.options.max_retries {
+                        let delay = self.backoff(attempt);
+                        attempt += 1;
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    return Err(Error::UnexpectedError(err.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Send `request` and deserialize its body, retrying transient failures
+    /// *including* a body-decode error.
+    ///
+    /// A JSON deserialization failure usually means a partial response from a
+    /// node behind a load balancer, so it is retried like a transport error.
+    /// This is only safe for idempotent reads — callers that POST (e.g. the
+    /// faucet mint) must read the body themselves to avoid re-issuing the
+    /// request.
+    pub async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, Error> {
+        let mut attempt = 0;
+        loop {
+            let builder = request.try_clone().ok_or_else(|| {
+                Error::UnexpectedError("Request body is not retryable (not cloneable)".to_string())
+            })?;
+
+            let response = self.send(builder).await?;
+            match response.json::<T>().await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    if self.policy.should_retry_error(&err) && attempt < self.options.max_retries {
+                        let delay = self.backoff(attempt);
+                        attempt += 1;
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    return Err(Error::UnexpectedError(err.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Exponential backoff with full jitter: a random duration in
+    /// `[0, base * 2^attempt]`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .options
+            .retry_backoff_ms
+            .saturating_mul(1u64 << attempt.min(16));
+        let millis = rand::thread_rng().gen_range(0..=ceiling);
+        Duration::from_millis(millis)
+    }
+
+    /// Honor a `Retry-After` header, in either the whole-seconds form or the
+    /// HTTP-date (IMF-fixdate) form that load balancers commonly emit.
+    fn retry_after(&self, response: &Response) -> Option<Duration> {
+        let value = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        // `Retry-After: Wed, 21 Oct 2015 07:28:00 GMT` — the delay is the gap
+        // between that instant and now, clamped to zero if already in the past.
+        let target = parse_http_date(value)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(Duration::from_secs(target.saturating_sub(now)))
+    }
+}
+
+/// Parse an HTTP-date in IMF-fixdate form (e.g. `Wed, 21 Oct 2015 07:28:00 GMT`)
+/// into seconds since the Unix epoch.
+fn parse_http_date(value: &str) -> Option<u64> {
+    // "Wed, 21 Oct 2015 07:28:00 GMT" -> ["Wed,", "21", "Oct", "2015", "07:28:00", "GMT"]
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != 6 {
+        return None;
+    }
+
+    let day: i64 = parts[1].parse().ok()?;
+    let month = match parts[2] {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts[3].parse().ok()?;
+
+    let time: Vec<&str> = parts[4].split(':').collect();
+    if time.len() != 3 {
+        return None;
+    }
+    let hour: i64 = time[0].parse().ok()?;
+    let minute: i64 = time[1].parse().ok()?;
+    let second: i64 = time[2].parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    u64::try_from(seconds).ok()
+}
+
+/// Days since the Unix epoch for a proleptic-Gregorian date, using Howard
+/// Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}